@@ -0,0 +1,106 @@
+//! Interface enumeration and source-interface selection.
+//!
+//! On a multi-homed machine the default route may not reach the target LAN,
+//! so the sender needs to pick which local interface to transmit from. This
+//! module lists up, non-loopback interfaces (name, index, MAC, IPv4 and
+//! broadcast address) so the CLI can offer `--list-interfaces`, bind its UDP
+//! socket to a chosen interface's address via `--iface`. Raw-mode sends (see
+//! [`crate::raw`]) do not use this module; they look up the source MAC and
+//! ifindex of `--iface` themselves.
+
+use std::ffi::CStr;
+use std::io;
+use std::net::Ipv4Addr;
+use std::ptr;
+
+use libc::{freeifaddrs, getifaddrs, ifaddrs, sockaddr_in, sockaddr_ll, AF_INET, AF_PACKET};
+
+/// A discovered network interface.
+#[derive(Debug, Clone)]
+pub struct Interface {
+    pub name: String,
+    pub index: u32,
+    pub mac: Option<[u8; 6]>,
+    pub ipv4: Option<Ipv4Addr>,
+    pub broadcast: Option<Ipv4Addr>,
+}
+
+/// List up, non-loopback interfaces known to the OS.
+///
+/// `getifaddrs` reports one entry per address family per interface, so
+/// entries are merged by name into a single [`Interface`] each.
+///
+/// # Errors
+/// Will return `Err` if the OS is unable to enumerate interfaces.
+pub fn list() -> io::Result<Vec<Interface>> {
+    let mut addrs: *mut ifaddrs = ptr::null_mut();
+    if unsafe { getifaddrs(&mut addrs) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut interfaces: Vec<Interface> = Vec::new();
+    let mut cursor = addrs;
+    while !cursor.is_null() {
+        let ifa = unsafe { &*cursor };
+        cursor = ifa.ifa_next;
+
+        let flags = ifa.ifa_flags as i32;
+        if flags & libc::IFF_UP == 0 || flags & libc::IFF_LOOPBACK != 0 {
+            continue;
+        }
+        if ifa.ifa_name.is_null() {
+            continue;
+        }
+        let name = unsafe { CStr::from_ptr(ifa.ifa_name) }
+            .to_string_lossy()
+            .into_owned();
+
+        let idx = match interfaces.iter().position(|i| i.name == name) {
+            Some(idx) => idx,
+            None => {
+                let index = unsafe { libc::if_nametoindex(ifa.ifa_name) };
+                interfaces.push(Interface {
+                    name,
+                    index,
+                    mac: None,
+                    ipv4: None,
+                    broadcast: None,
+                });
+                interfaces.len() - 1
+            }
+        };
+
+        if ifa.ifa_addr.is_null() {
+            continue;
+        }
+        let family = i32::from(unsafe { (*ifa.ifa_addr).sa_family });
+        if family == AF_INET {
+            let sin = unsafe { &*ifa.ifa_addr.cast::<sockaddr_in>() };
+            interfaces[idx].ipv4 = Some(Ipv4Addr::from(u32::from_be(sin.sin_addr.s_addr)));
+
+            if flags & libc::IFF_BROADCAST != 0 && !ifa.ifa_ifu.is_null() {
+                let bsin = unsafe { &*ifa.ifa_ifu.cast::<sockaddr_in>() };
+                interfaces[idx].broadcast =
+                    Some(Ipv4Addr::from(u32::from_be(bsin.sin_addr.s_addr)));
+            }
+        } else if family == AF_PACKET {
+            let sll = unsafe { &*ifa.ifa_addr.cast::<sockaddr_ll>() };
+            if sll.sll_halen >= 6 {
+                let mut mac = [0u8; 6];
+                mac.copy_from_slice(&sll.sll_addr[..6]);
+                interfaces[idx].mac = Some(mac);
+            }
+        }
+    }
+
+    unsafe { freeifaddrs(addrs) };
+    Ok(interfaces)
+}
+
+/// Look up a single up, non-loopback interface by name.
+///
+/// # Errors
+/// Will return `Err` if the OS is unable to enumerate interfaces.
+pub fn find(name: &str) -> io::Result<Option<Interface>> {
+    Ok(list()?.into_iter().find(|i| i.name == name))
+}
@@ -0,0 +1,119 @@
+//! Raw Ethernet (EtherType `0x0842`) transmission of Wake-on-LAN frames.
+//!
+//! Sending over UDP requires a cooperating router for directed broadcast and
+//! cannot reach hosts that only listen at layer 2. This module wraps the
+//! magic packet in an Ethernet II frame and transmits it directly on a named
+//! interface via an `AF_PACKET`/`SOCK_RAW` socket.
+
+use std::ffi::CString;
+use std::io;
+use std::mem;
+
+use libc::{c_int, ifreq, sockaddr_ll, AF_PACKET, ETH_ALEN, SOCK_RAW};
+
+/// EtherType reserved for Wake-on-LAN magic packets sent over raw Ethernet.
+const ETHERTYPE_WOL: u16 = 0x0842;
+
+/// Destination used for a broadcast raw frame.
+const BROADCAST_MAC: [u8; 6] = [0xff; 6];
+
+/// Look up an interface's index and MAC address by name, using `fd` (an
+/// already-open socket) to issue the ioctls.
+fn interface_info(fd: c_int, name: &str) -> io::Result<(c_int, [u8; 6])> {
+    let cname = CString::new(name).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "interface name contains a nul byte",
+        )
+    })?;
+    let bytes = cname.as_bytes_with_nul();
+
+    let mut ifr: ifreq = unsafe { mem::zeroed() };
+    if bytes.len() > ifr.ifr_name.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "interface name too long",
+        ));
+    }
+    for (dst, src) in ifr.ifr_name.iter_mut().zip(bytes.iter()) {
+        *dst = *src as libc::c_char;
+    }
+
+    if unsafe { libc::ioctl(fd, libc::SIOCGIFINDEX, &mut ifr) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let index = unsafe { ifr.ifr_ifru.ifru_ivalue };
+
+    if unsafe { libc::ioctl(fd, libc::SIOCGIFHWADDR, &mut ifr) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let hwaddr = unsafe { ifr.ifr_ifru.ifru_hwaddr };
+    let mut mac = [0u8; 6];
+    for (dst, src) in mac.iter_mut().zip(hwaddr.sa_data.iter()) {
+        *dst = *src as u8;
+    }
+
+    Ok((index, mac))
+}
+
+/// Send a Wake-on-LAN `payload` (the magic packet, optionally with a
+/// SecureOn password appended) as a raw Ethernet II frame on `iface`,
+/// bypassing UDP/IP entirely.
+///
+/// The frame's destination is `dst`, unless `broadcast` is set, in which
+/// case `ff:ff:ff:ff:ff:ff` is used instead. The frame's source is the MAC
+/// address of `iface`.
+///
+/// # Errors
+/// Will return `Err` if the interface cannot be found, or the OS is unable
+/// to create or use a raw `AF_PACKET` socket (typically requires elevated
+/// privileges).
+pub fn wake_on_lan_raw(
+    payload: &[u8],
+    dst: [u8; 6],
+    broadcast: bool,
+    iface: &str,
+) -> io::Result<()> {
+    let dst = if broadcast { BROADCAST_MAC } else { dst };
+
+    let protocol = ETHERTYPE_WOL.to_be() as c_int;
+    let fd = unsafe { libc::socket(AF_PACKET, SOCK_RAW, protocol) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let result = (|| {
+        let (index, src) = interface_info(fd, iface)?;
+
+        let mut frame = Vec::with_capacity(14 + payload.len());
+        frame.extend_from_slice(&dst);
+        frame.extend_from_slice(&src);
+        frame.extend_from_slice(&ETHERTYPE_WOL.to_be_bytes());
+        frame.extend_from_slice(payload);
+
+        let mut addr: sockaddr_ll = unsafe { mem::zeroed() };
+        addr.sll_family = AF_PACKET as u16;
+        addr.sll_protocol = ETHERTYPE_WOL.to_be();
+        addr.sll_ifindex = index;
+        addr.sll_halen = ETH_ALEN as u8;
+        addr.sll_addr[..6].copy_from_slice(&dst);
+
+        let sent = unsafe {
+            libc::sendto(
+                fd,
+                frame.as_ptr().cast(),
+                frame.len(),
+                0,
+                std::ptr::addr_of!(addr).cast(),
+                mem::size_of::<sockaddr_ll>() as u32,
+            )
+        };
+        if sent < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    })();
+
+    unsafe { libc::close(fd) };
+    result
+}
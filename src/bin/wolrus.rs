@@ -1,7 +1,101 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddrV4, UdpSocket};
+use std::process::ExitCode;
+
 use clap::Parser;
-use core::net::Ipv4Addr;
 use macaddr::MacAddr6;
-use wolrus::wake_on_lan;
+
+mod iface;
+mod raw;
+
+/// A SecureOn password, given in colon-hex form (e.g. `aa:bb:cc:dd` or
+/// `aa:bb:cc:dd:ee:ff`), same grammar as a MAC address.
+#[derive(Debug, Clone)]
+struct Password(Vec<u8>);
+
+#[derive(Debug)]
+struct ParsePasswordError(String);
+
+impl fmt::Display for ParsePasswordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for ParsePasswordError {}
+
+impl std::str::FromStr for Password {
+    type Err = ParsePasswordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = s
+            .split(':')
+            .map(|part| {
+                u8::from_str_radix(part, 16)
+                    .map_err(|_| ParsePasswordError(format!("invalid hex byte: {part}")))
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        match bytes.len() {
+            4 | 6 => Ok(Self(bytes)),
+            n => Err(ParsePasswordError(format!(
+                "password must be 4 or 6 bytes, got {n}"
+            ))),
+        }
+    }
+}
+
+/// A target host address in CIDR form (e.g. `10.1.2.3/24`), parsed into that
+/// subnet's directed broadcast address (e.g. `10.1.2.255`).
+#[derive(Debug, Clone, Copy)]
+struct DirectedBroadcast(Ipv4Addr);
+
+#[derive(Debug)]
+struct ParseCidrError(String);
+
+impl fmt::Display for ParseCidrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for ParseCidrError {}
+
+impl std::str::FromStr for DirectedBroadcast {
+    type Err = ParseCidrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix) = s
+            .split_once('/')
+            .ok_or_else(|| ParseCidrError(format!("expected ADDR/PREFIX, got `{s}`")))?;
+        let addr: Ipv4Addr = addr
+            .parse()
+            .map_err(|_| ParseCidrError(format!("invalid IPv4 address: {addr}")))?;
+        let prefix: u8 = prefix
+            .parse()
+            .map_err(|_| ParseCidrError(format!("invalid prefix length: {prefix}")))?;
+
+        if prefix > 32 {
+            return Err(ParseCidrError(format!(
+                "prefix length must be 0-32, got {prefix}"
+            )));
+        }
+        if prefix >= 31 {
+            return Err(ParseCidrError(format!(
+                "/{prefix} has no usable directed broadcast address"
+            )));
+        }
+
+        let mask = if prefix == 0 {
+            0
+        } else {
+            u32::MAX << (32 - prefix)
+        };
+        let broadcast = (u32::from(addr) & mask) | !mask;
+        Ok(Self(Ipv4Addr::from(broadcast)))
+    }
+}
 
 /// Send wake-on-lan packets.
 ///
@@ -9,30 +103,240 @@ use wolrus::wake_on_lan;
 /// support in destination computer; most 802.11 wireless interfaces do not
 /// maintain a link in low-power states and cannot receive a magic packet.
 #[derive(Parser, Debug)]
-pub struct Args {
-    /// Target NIC 48-bit MAC address
-    #[arg()]
-    pub mac: MacAddr6,
+struct Args {
+    /// Target NIC 48-bit MAC address(es)
+    ///
+    /// Not required when passing `--list-interfaces`.
+    mac: Vec<MacAddr6>,
 
     /// Target IP address
     ///
     /// Hint: For a NIC on a local subnet 192.168.10.0/24, use the subnet's
     /// broadcast address: 192.168.10.255.
     #[arg(short = 'i', long, default_value_t = Ipv4Addr::BROADCAST)]
-    pub ip: Ipv4Addr,
+    ip: Ipv4Addr,
+
+    /// Target host address in CIDR form, e.g. `10.1.2.3/24`
+    ///
+    /// Derives the subnet's directed broadcast address to use as the
+    /// destination IP, overriding `--ip`.
+    #[arg(short = 'd', long = "cidr", value_name = "ADDR/PREFIX")]
+    cidr: Option<DirectedBroadcast>,
 
     /// Target port; usually 0, 7 (Echo), or 9 (Discard)
     #[arg(short = 'p', long, default_value_t = 9)]
-    pub port: u16,
+    port: u16,
+
+    /// SecureOn password, as `aa:bb:cc:dd` (4 bytes) or `aa:bb:cc:dd:ee:ff` (6 bytes)
+    #[arg(short = 's', long)]
+    password: Option<Password>,
+
+    /// Send as a raw Ethernet II frame (EtherType 0x0842) instead of over UDP
+    ///
+    /// Requires `--iface`, and typically requires elevated privileges to
+    /// open a raw socket.
+    #[arg(long)]
+    raw: bool,
+
+    /// Interface to send from, e.g. `eth0`
+    ///
+    /// With `--raw`, the frame is sent out of this interface, with its MAC
+    /// as the frame's source address. Without `--raw`, the UDP socket is bound
+    /// to this interface's IPv4 address instead of `0.0.0.0`, which matters
+    /// on a multi-homed machine whose default route doesn't reach the
+    /// target LAN.
+    #[arg(long)]
+    iface: Option<String>,
+
+    /// In raw mode, send to ff:ff:ff:ff:ff:ff instead of the target MAC
+    #[arg(long)]
+    broadcast: bool,
+
+    /// List candidate interfaces (name, index, MAC, IPv4/broadcast address) and exit
+    #[arg(long)]
+    list_interfaces: bool,
 }
 
-fn main() {
+/// Build a magic Wake-on-LAN packet: 6 bytes of `0xff`, followed by 16
+/// repetitions of `mac`, followed by an optional SecureOn password.
+///
+/// The `wolrus` lib builds the same packet, but only to send it over a bare
+/// `smoltcp` UDP socket with no real device behind it, which never actually
+/// reaches the wire. Both send paths here need a real OS socket instead, so
+/// they build the packet locally rather than going through the lib.
+fn build_magic_packet(mac: MacAddr6, password: Option<&[u8]>) -> Vec<u8> {
+    let mut packet = [&[0xffu8; 6], mac.as_bytes().repeat(16).as_slice()].concat();
+    if let Some(password) = password {
+        packet.extend_from_slice(password);
+    }
+    packet
+}
+
+/// Send a Wake-on-LAN packet over UDP, from a real `std::net::UdpSocket`.
+fn send_wol(
+    ip: Ipv4Addr,
+    port: u16,
+    mac: MacAddr6,
+    password: Option<&[u8]>,
+    bind_addr: Option<Ipv4Addr>,
+) -> io::Result<()> {
+    let bind_addr = bind_addr.unwrap_or(Ipv4Addr::UNSPECIFIED);
+    let socket = UdpSocket::bind(SocketAddrV4::new(bind_addr, 0))?;
+
+    // Permits sending of broadcast messages.
+    socket.set_broadcast(true)?;
+
+    // Connect to target host.
+    let target = SocketAddrV4::new(ip, port);
+    socket.connect(target)?;
+
+    // Send WOL magic packet.
+    let packet = build_magic_packet(mac, password);
+    socket.send(&packet)?;
+    Ok(())
+}
+
+/// Send one magic packet per `args.mac`, returning the MACs that failed to
+/// send.
+///
+/// `iface` is the raw-mode interface name, already validated by the caller
+/// to be present when `args.raw` is set.
+fn wake_targets(
+    args: &Args,
+    ip: Ipv4Addr,
+    password: Option<&Password>,
+    bind_addr: Option<Ipv4Addr>,
+    iface: Option<&str>,
+) -> Vec<(MacAddr6, io::Error)> {
+    let password = password.map(|p| p.0.as_slice());
+    args.mac
+        .iter()
+        .filter_map(|&mac| {
+            let result = if args.raw {
+                let packet = build_magic_packet(mac, password);
+                raw::wake_on_lan_raw(
+                    &packet,
+                    mac.into_array(),
+                    args.broadcast,
+                    iface.expect("checked by caller"),
+                )
+            } else {
+                send_wol(ip, args.port, mac, password, bind_addr)
+            };
+            result.err().map(|err| (mac, err))
+        })
+        .collect()
+}
+
+/// Print candidate interfaces to send Wake-on-LAN packets from.
+fn print_interfaces() -> io::Result<()> {
+    for i in iface::list()? {
+        println!(
+            "{} (index {}) mac={} ipv4={} broadcast={}",
+            i.name,
+            i.index,
+            i.mac.map_or("-".to_string(), |m| {
+                m.iter()
+                    .map(|b| format!("{b:02x}"))
+                    .collect::<Vec<_>>()
+                    .join(":")
+            }),
+            i.ipv4.map_or("-".to_string(), |a| a.to_string()),
+            i.broadcast.map_or("-".to_string(), |a| a.to_string()),
+        );
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
     let args = Args::parse();
 
-    let ip = args.ip.octets();
-    let mac = args.mac.into_array();
+    if args.list_interfaces {
+        if let Err(err) = print_interfaces() {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    if args.mac.is_empty() {
+        eprintln!("no target MAC address given");
+        return ExitCode::FAILURE;
+    }
+
+    if args.raw && args.iface.is_none() {
+        eprintln!("--raw requires --iface <IFNAME>");
+        return ExitCode::FAILURE;
+    }
+
+    let bind_addr = if args.raw {
+        None
+    } else if let Some(name) = args.iface.as_deref() {
+        match iface::find(name) {
+            Ok(Some(i)) if i.ipv4.is_some() => i.ipv4,
+            Ok(Some(i)) => {
+                eprintln!("interface {} has no IPv4 address", i.name);
+                return ExitCode::FAILURE;
+            }
+            Ok(None) => {
+                eprintln!("no such interface: {name}");
+                return ExitCode::FAILURE;
+            }
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        None
+    };
+
+    let ip = args.cidr.map_or(args.ip, |b| b.0);
+    let password = args.password.as_ref();
+    let total = args.mac.len();
+
+    let failures = wake_targets(&args, ip, password, bind_addr, args.iface.as_deref());
+
+    if failures.is_empty() {
+        if total > 1 {
+            println!("sent magic packet to {total} targets");
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    for (mac, err) in &failures {
+        eprintln!("{mac}: {err}");
+    }
+    eprintln!("failed to wake {} of {total} target(s)", failures.len());
+    ExitCode::FAILURE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DirectedBroadcast;
+
+    #[test]
+    fn directed_broadcast_mid_range_prefix() {
+        let b: DirectedBroadcast = "192.168.10.42/24".parse().unwrap();
+        assert_eq!(b.0, "192.168.10.255".parse::<std::net::Ipv4Addr>().unwrap());
+    }
+
+    #[test]
+    fn directed_broadcast_prefix_zero() {
+        let b: DirectedBroadcast = "10.1.2.3/0".parse().unwrap();
+        assert_eq!(
+            b.0,
+            "255.255.255.255".parse::<std::net::Ipv4Addr>().unwrap()
+        );
+    }
+
+    #[test]
+    fn directed_broadcast_rejects_slash_31() {
+        assert!("10.1.2.3/31".parse::<DirectedBroadcast>().is_err());
+    }
 
-    if let Err(err) = wake_on_lan(mac, Some(ip), Some(args.port)) {
-        eprintln!("{err:?}");
+    #[test]
+    fn directed_broadcast_rejects_slash_32() {
+        assert!("10.1.2.3/32".parse::<DirectedBroadcast>().is_err());
     }
 }
@@ -10,14 +10,18 @@
 //! # Examples
 //!
 //! ```rust
-//! use wolrus::wake_on_lan;
+//! use wolrus::{wake_on_lan, SecureOnPassword};
 //!
 //! // Broadcast WoL on the local network.
 //! let mac = [0, 1, 2, 3, 4, 5];
-//! wake_on_lan(mac, None, None).expect("failed to send packet");
+//! wake_on_lan(mac, None, None, None).expect("failed to send packet");
 //!
 //! // Broadcast WoL on the local subnet.
-//! wake_on_lan(mac, Some([192, 168, 0, 255]), None).expect("failed to send packet");
+//! wake_on_lan(mac, Some([192, 168, 0, 255]), None, None).expect("failed to send packet");
+//!
+//! // Broadcast WoL with a SecureOn password.
+//! let password = SecureOnPassword::Six([0, 1, 2, 3, 4, 5]);
+//! wake_on_lan(mac, None, None, Some(password)).expect("failed to send packet");
 //! ```
 //! [Limitations]: https://en.wikipedia.org/wiki/Wake-on-LAN#Magic_packet
 
@@ -40,50 +44,98 @@ const BIND_ADDR: IpAddress = IpAddress::Ipv4(Ipv4Address::UNSPECIFIED);
 /// Magic packet length in number of bytes.
 const MAGIC_PACKET_LENGTH: usize = 102; // 6 + 6 * 16 = 102
 
+/// Maximum length of a SecureOn password, in bytes.
+const MAX_PASSWORD_LENGTH: usize = 6;
+
+/// Maximum possible packet length: the magic packet plus a SecureOn password.
+const MAX_PACKET_LENGTH: usize = MAGIC_PACKET_LENGTH + MAX_PASSWORD_LENGTH;
+
 #[derive(Debug)]
 pub enum Error {
     BindError(UdpBindError),
     SendError(UdpSendError),
 }
 
-/// Build a magic Wake-on-LAN packet from a 48-bit MAC address.
+/// A SecureOn password, appended to the magic packet so that only NICs
+/// configured with a matching password will wake.
+///
+/// SecureOn is supported by some NICs (e.g. certain AMD/Realtek chipsets) as
+/// a 4- or 6-byte value, conventionally written like an IPv4 address or a
+/// MAC address respectively.
+#[derive(Debug, Clone, Copy)]
+pub enum SecureOnPassword {
+    /// 4-byte password, conventionally written like an IPv4 address.
+    Four([u8; 4]),
+    /// 6-byte password, conventionally written like a MAC address.
+    Six([u8; 6]),
+}
+
+impl SecureOnPassword {
+    fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Four(bytes) => bytes,
+            Self::Six(bytes) => bytes,
+        }
+    }
+}
+
+/// Build a magic Wake-on-LAN packet from a 48-bit MAC address, optionally
+/// followed by a SecureOn password.
+///
+/// Returns a fixed-size buffer sized for the largest possible packet, along
+/// with the number of leading bytes that are populated.
 #[inline]
-fn build_magic_packet(mac: [u8; 6]) -> [u8; MAGIC_PACKET_LENGTH] {
+fn build_magic_packet(
+    mac: [u8; 6],
+    password: Option<SecureOnPassword>,
+) -> ([u8; MAX_PACKET_LENGTH], usize) {
     // The first 6 bytes if the packet bytes are all 0xff, followed by 16
     // repetitions of the 6-byte MAC address.
-    let mut packet = [0xff; MAGIC_PACKET_LENGTH];
+    let mut packet = [0xff; MAX_PACKET_LENGTH];
 
     // SAFETY: The slice length is constructed as a multiple of 6-byte arrays, 17 to be exact.
-    let chunks = unsafe { packet.as_chunks_unchecked_mut() };
+    let chunks = unsafe { packet[..MAGIC_PACKET_LENGTH].as_chunks_unchecked_mut() };
 
     // Fill the packet array with repetitions of the MAC-address, except the first 6 bytes.
     // TODO: Make fn const when feature `const_slice_make_iter` is stabilised.
     for chunk in chunks.iter_mut().skip(1) {
         *chunk = mac;
     }
-    packet
+
+    let len = match password {
+        Some(password) => {
+            let bytes = password.as_bytes();
+            packet[MAGIC_PACKET_LENGTH..MAGIC_PACKET_LENGTH + bytes.len()].copy_from_slice(bytes);
+            MAGIC_PACKET_LENGTH + bytes.len()
+        }
+        None => MAGIC_PACKET_LENGTH,
+    };
+    (packet, len)
 }
 
 /// Send a Wake-on-LAN packet over UDP.
 ///
 /// The function creates a UDP socket bound to `0.0.0.0:0` and sends a
 /// Wake-on-LAN UDP datagram to the specified `ip` and `port`, or default
-/// `255.255.255.255` on port `9`.
+/// `255.255.255.255` on port `9`. If `password` is given, it is appended to
+/// the magic packet as a SecureOn password.
+///
+/// This is a convenience wrapper around [`wake_on_lan_with`] for callers
+/// who don't already own a socket; see that function to reuse an existing
+/// one instead.
 ///
 /// # Errors
 /// Will return `Err` if the OS is unable to create a socket.
-pub fn wake_on_lan(mac: [u8; 6], ip: Option<[u8; 4]>, port: Option<u16>) -> Result<(), Error> {
-    // Set destination endpoint.
-    let addr = match ip {
-        Some(ip) => IpAddress::Ipv4(ip.into()),
-        None => DEFAULT_ADDR,
-    };
-    let port = port.unwrap_or(DEFAULT_PORT);
-    let remote_endpoint = IpEndpoint::new(addr, port);
-
-    // Create UDP socket.
+pub fn wake_on_lan(
+    mac: [u8; 6],
+    ip: Option<[u8; 4]>,
+    port: Option<u16>,
+    password: Option<SecureOnPassword>,
+) -> Result<(), Error> {
+    // Create UDP socket. The tx buffer is sized for the largest possible
+    // packet (magic packet plus a 6-byte SecureOn password).
     let rx_buffer = UdpPacketBuffer::new([UdpPacketMetadata::EMPTY; 4], [0u8; 0]); // no receive
-    let tx_storage = ManagedSlice::Borrowed(&mut [0u8; MAGIC_PACKET_LENGTH]);
+    let tx_storage = ManagedSlice::Borrowed(&mut [0u8; MAX_PACKET_LENGTH]);
     let tx_buffer = UdpPacketBuffer::new([UdpPacketMetadata::EMPTY; 4], tx_storage);
     let mut socket = UdpSocket::new(rx_buffer, tx_buffer);
 
@@ -92,17 +144,45 @@ pub fn wake_on_lan(mac: [u8; 6], ip: Option<[u8; 4]>, port: Option<u16>) -> Resu
         .bind(IpEndpoint::new(BIND_ADDR, 12345))
         .map_err(Error::BindError)?;
 
-    // Send WOL magic packet.
-    let packet = build_magic_packet(mac);
+    wake_on_lan_with(&mut socket, mac, ip, port, password)
+}
+
+/// Build a Wake-on-LAN magic packet and send it over a caller-provided,
+/// already-bound UDP socket.
+///
+/// Unlike [`wake_on_lan`], this does not create, size, or bind a socket — it
+/// only builds the packet and calls [`UdpSocket::send_slice`]. This lets the
+/// crate be embedded in an existing smoltcp stack (analogous to passing an
+/// existing file descriptor to libpnet), where the interface, sockets, and
+/// device polling are already managed by the caller, such as on a
+/// microcontroller.
+///
+/// # Errors
+/// Will return `Err` if the socket is unable to send the packet, e.g.
+/// because its tx buffer is too small for the packet.
+pub fn wake_on_lan_with(
+    socket: &mut UdpSocket,
+    mac: [u8; 6],
+    ip: Option<[u8; 4]>,
+    port: Option<u16>,
+    password: Option<SecureOnPassword>,
+) -> Result<(), Error> {
+    let addr = match ip {
+        Some(ip) => IpAddress::Ipv4(ip.into()),
+        None => DEFAULT_ADDR,
+    };
+    let port = port.unwrap_or(DEFAULT_PORT);
+    let remote_endpoint = IpEndpoint::new(addr, port);
+
+    let (packet, len) = build_magic_packet(mac, password);
     socket
-        .send_slice(&packet, remote_endpoint)
-        .map_err(Error::SendError)?;
-    Ok(())
+        .send_slice(&packet[..len], remote_endpoint)
+        .map_err(Error::SendError)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::build_magic_packet;
+    use crate::{build_magic_packet, SecureOnPassword};
     use heapless::Vec;
 
     // A WoL packet is 6 bytes of 0xff, followed by 16 repetitions of the
@@ -114,10 +194,10 @@ mod tests {
         let mac = [0xff; 6]; // broadcast
         let expected = [0xffu8; EXPECTED_LEN];
 
-        let packet = build_magic_packet(mac);
+        let (packet, len) = build_magic_packet(mac, None);
 
-        assert_eq!(packet.len(), EXPECTED_LEN);
-        assert_eq!(packet.as_slice(), expected.as_slice());
+        assert_eq!(len, EXPECTED_LEN);
+        assert_eq!(&packet[..len], expected.as_slice());
     }
 
     #[test]
@@ -131,9 +211,34 @@ mod tests {
             .extend_from_slice(mac.repeat(16).as_slice())
             .expect("should fit in capacity");
 
-        let packet = build_magic_packet(mac);
+        let (packet, len) = build_magic_packet(mac, None);
+
+        assert_eq!(len, EXPECTED_LEN);
+        assert_eq!(&packet[..len], expected.as_slice());
+    }
+
+    #[test]
+    fn build_packet_with_four_byte_password() {
+        let mac = [0, 1, 2, 3, 4, 5];
+        let password = SecureOnPassword::Four([10, 0, 0, 1]);
+
+        let (packet, len) = build_magic_packet(mac, Some(password));
+
+        assert_eq!(len, EXPECTED_LEN + 4);
+        assert_eq!(&packet[EXPECTED_LEN..len], &[10, 0, 0, 1]);
+    }
+
+    #[test]
+    fn build_packet_with_six_byte_password() {
+        let mac = [0, 1, 2, 3, 4, 5];
+        let password = SecureOnPassword::Six([0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff]);
+
+        let (packet, len) = build_magic_packet(mac, Some(password));
 
-        assert_eq!(packet.len(), EXPECTED_LEN);
-        assert_eq!(packet.as_slice(), expected.as_slice());
+        assert_eq!(len, EXPECTED_LEN + 6);
+        assert_eq!(
+            &packet[EXPECTED_LEN..len],
+            &[0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff]
+        );
     }
 }